@@ -0,0 +1,223 @@
+//! Bell 202 AFSK bit-slicer
+//!
+//! SAME bursts are sent as asynchronous Bell 202 AFSK: a mark tone
+//! of 2083.3 Hz represents a `1` bit and a space tone of 1562.5 Hz
+//! represents a `0` bit, at 520.83 baud. [`Demodulator`] turns a
+//! stream of PCM samples into a stream of bits.
+//!
+//! The incoming stream is not assumed to start on a bit boundary --
+//! a real capture (sound card, TCP audio, a file) never does. Each
+//! sample is run through a pair of
+//! [Goertzel](https://en.wikipedia.org/wiki/Goertzel_algorithm) tone
+//! detectors evaluated over a sliding window, giving an oversampled
+//! mark/space decision every sample. A small digital clock recovery
+//! loop then decides *when*, within that continuous stream, to latch
+//! a bit: it nudges its phase toward the center of the bit cell every
+//! time the oversampled decision flips, so it locks onto the
+//! transmitter's bit clock after a few preamble transitions instead
+//! of requiring the caller to hand it pre-aligned samples.
+
+use std::collections::VecDeque;
+
+/// Mark ("1") tone frequency, Hz
+pub const MARK_HZ: f32 = 2083.3;
+
+/// Space ("0") tone frequency, Hz
+pub const SPACE_HZ: f32 = 1562.5;
+
+/// SAME/EAS baud rate, bits/sec
+pub const BAUD: f32 = 520.83;
+
+/// Number of samples making up one bit period at `sample_rate`
+///
+/// Shared with [`crate::transmit`] so the encoder and decoder agree
+/// on exactly how many samples a bit occupies.
+pub(crate) fn samples_per_bit(sample_rate: u32) -> usize {
+    (((sample_rate as f32) / BAUD).round() as usize).max(1)
+}
+
+/// Demodulates a Bell 202 AFSK sample stream into bits
+///
+/// Samples are pushed one at a time via [`Demodulator::push_sample`].
+/// `Demodulator` owns both the tone discriminator and the bit clock
+/// recovery loop, so it can be fed a sample stream starting at any
+/// phase and will synchronize to the bit clock on its own.
+pub struct Demodulator {
+    sample_rate: u32,
+    samples_per_bit: usize,
+    /// trailing window of normalized samples, always the most recent
+    /// `samples_per_bit` of them once primed
+    window: VecDeque<f32>,
+    /// oversampled mark/space decision from the previous sample
+    last_decision: Option<bool>,
+    /// fractional position within the current bit cell, in samples
+    phase: f32,
+}
+
+impl Demodulator {
+    /// Create a demodulator for samples taken at `sample_rate` Hz
+    pub fn new(sample_rate: u32) -> Self {
+        let samples_per_bit = samples_per_bit(sample_rate);
+        Demodulator {
+            sample_rate,
+            samples_per_bit,
+            window: VecDeque::with_capacity(samples_per_bit),
+            last_decision: None,
+            phase: 0.0,
+        }
+    }
+
+    /// The sample rate this demodulator was constructed with
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Push one PCM sample
+    ///
+    /// Returns a synchronized bit once per recovered bit period.
+    /// Every polarity change in the oversampled mark/space
+    /// discriminator pulls the bit clock's phase toward the center
+    /// of the bit cell, so the demodulator locks onto the
+    /// transmitter's clock after a handful of preamble transitions
+    /// rather than assuming sample zero is a bit boundary.
+    pub fn push_sample(&mut self, sample: i16) -> Option<bool> {
+        self.window.push_back(sample as f32 / i16::MAX as f32);
+        if self.window.len() > self.samples_per_bit {
+            self.window.pop_front();
+        }
+        // the window just reached its first full bit period: this
+        // decision already covers a whole bit cell with no lag, so
+        // it's ready to latch immediately instead of waiting a
+        // further bit period for `phase` to reach threshold
+        let just_primed = self.window.len() == self.samples_per_bit && self.last_decision.is_none();
+        if self.window.len() < self.samples_per_bit {
+            return None;
+        }
+
+        let samples = self.window.make_contiguous();
+        let mark = goertzel_power(samples, MARK_HZ, self.sample_rate);
+        let space = goertzel_power(samples, SPACE_HZ, self.sample_rate);
+        let decision = mark > space;
+
+        if just_primed {
+            self.last_decision = Some(decision);
+            return Some(decision);
+        }
+
+        if self.last_decision.replace(decision) == Some(!decision) {
+            // a transition in an NRZ signal can only happen at a
+            // bit cell boundary. The discriminator's sliding window
+            // spans one whole bit period, so its output lags the
+            // true tone transition by about half a bit period; jump
+            // the clock to the matching point in the new cell
+            // instead of letting it free-run from a stale phase
+            self.phase = self.samples_per_bit as f32 / 2.0;
+        } else {
+            self.phase += 1.0;
+        }
+
+        if self.phase >= self.samples_per_bit as f32 {
+            self.phase -= self.samples_per_bit as f32;
+            Some(decision)
+        } else {
+            None
+        }
+    }
+}
+
+/// Goertzel-algorithm power of `samples` at `freq_hz`
+fn goertzel_power(samples: &[f32], freq_hz: f32, sample_rate: u32) -> f32 {
+    let n = samples.len() as f32;
+    let k = (0.5 + n * freq_hz / sample_rate as f32).floor();
+    let omega = (2.0 * std::f32::consts::PI / n) * k;
+    let coeff = 2.0 * omega.cos();
+
+    let (mut q1, mut q2) = (0.0f32, 0.0f32);
+    for &sample in samples {
+        let q0 = coeff * q1 - q2 + sample;
+        q2 = q1;
+        q1 = q0;
+    }
+
+    q1 * q1 + q2 * q2 - q1 * q2 * coeff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: u32 = 22_050;
+
+    fn tone(freq_hz: f32, sample_rate: u32, n: usize) -> Vec<i16> {
+        (0..n)
+            .map(|i| {
+                let t = i as f32 / sample_rate as f32;
+                let s = (2.0 * std::f32::consts::PI * freq_hz * t).sin();
+                (s * i16::MAX as f32) as i16
+            })
+            .collect()
+    }
+
+    #[test]
+    fn distinguishes_mark_from_space() {
+        let mut demod = Demodulator::new(SAMPLE_RATE);
+        let samples_per_bit = demod.samples_per_bit;
+
+        let mut mark_bit = None;
+        for sample in tone(MARK_HZ, SAMPLE_RATE, samples_per_bit * 3) {
+            mark_bit = demod.push_sample(sample).or(mark_bit);
+        }
+        assert_eq!(mark_bit, Some(true));
+
+        let mut space_bit = None;
+        for sample in tone(SPACE_HZ, SAMPLE_RATE, samples_per_bit * 3) {
+            space_bit = demod.push_sample(sample).or(space_bit);
+        }
+        assert_eq!(space_bit, Some(false));
+    }
+
+    #[test]
+    fn locks_onto_the_bit_clock_regardless_of_starting_phase() {
+        // an alternating mark/space bit pattern, the same kind of
+        // transition-rich signal a SAME preamble provides
+        let bits = [true, false, true, false, true, false, true, false];
+
+        for phase_offset in 0..50 {
+            let mut demod = Demodulator::new(SAMPLE_RATE);
+            let samples_per_bit = demod.samples_per_bit;
+
+            // misalign the stream by `phase_offset` samples of
+            // leading space tone, as a real capture beginning
+            // mid-bit would
+            let mut samples = tone(SPACE_HZ, SAMPLE_RATE, phase_offset);
+            for &bit in &bits {
+                let freq = if bit { MARK_HZ } else { SPACE_HZ };
+                samples.extend(tone(freq, SAMPLE_RATE, samples_per_bit));
+            }
+
+            let decoded: Vec<bool> = samples
+                .into_iter()
+                .filter_map(|s| demod.push_sample(s))
+                .collect();
+
+            // regardless of `phase_offset`, the clock recovery loop
+            // should lock on within the first couple of bits and
+            // then track the rest of the alternating pattern
+            assert!(
+                decoded.len() >= bits.len() - 2,
+                "offset {}: only recovered {} of {} bits: {:?}",
+                phase_offset,
+                decoded.len(),
+                bits.len(),
+                decoded
+            );
+            assert_eq!(
+                &decoded[decoded.len() - (bits.len() - 2)..],
+                &bits[bits.len() - (bits.len() - 2)..],
+                "offset {}: tail of decoded bits diverged: {:?}",
+                phase_offset,
+                decoded
+            );
+        }
+    }
+}
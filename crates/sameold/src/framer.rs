@@ -0,0 +1,182 @@
+//! Burst framing
+//!
+//! [`Framer`] turns a stream of NRZ bits from [`crate::demod::Demodulator`]
+//! into completed burst header strings. Since SAME has no start/stop
+//! bits, the incoming bit stream doesn't arrive aligned to a byte
+//! boundary either: while no carrier has been found, `Framer` checks
+//! for the `0xAB` preamble byte at every bit position rather than
+//! every eighth one. Whichever bit the preamble is found on becomes
+//! byte zero for the rest of the burst, and the ASCII text after
+//! the preamble, up to and including the burst's final `-`, is
+//! handed up as a single burst.
+
+/// The byte repeated in a SAME preamble
+///
+/// Shared with [`crate::transmit`], which must emit the same
+/// preamble this framer looks for.
+pub(crate) const PREAMBLE_BYTE: u8 = 0xAB;
+
+/// Defensive upper bound on burst length, in bytes
+///
+/// Real SAME bursts are well under 100 bytes; this just keeps a
+/// run of line noise from growing `Framer`'s buffer forever.
+const MAX_BURST_LEN: usize = 252;
+
+/// Coarse framing status, mirrored into [`crate::receiver::FrameOut`]
+/// by the receiver
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FramerStatus {
+    /// No preamble has been seen recently
+    NoCarrier,
+    /// Preamble seen; waiting for the `ZCZC` burst prefix
+    Searching,
+    /// A burst is being read
+    Reading,
+}
+
+/// An event produced by [`Framer::push_bit`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FramerEvent {
+    /// The framing status changed
+    Status(FramerStatus),
+    /// A complete burst header was read, e.g.
+    /// `"ZCZC-WXR-TOR-027037+0030-1231800-KGGG/NWS-"`
+    Burst(String),
+}
+
+enum State {
+    NoCarrier,
+    Searching,
+    Reading(String),
+}
+
+/// Assembles NRZ bits into SAME burst header strings
+pub struct Framer {
+    shift: u8,
+    bit_count: u8,
+    state: State,
+}
+
+impl Framer {
+    /// Create a new, idle framer
+    pub fn new() -> Self {
+        Framer {
+            shift: 0,
+            bit_count: 0,
+            state: State::NoCarrier,
+        }
+    }
+
+    /// Push one demodulated bit
+    ///
+    /// Returns an event each time a byte boundary causes the
+    /// framing status to change, or a full burst is read.
+    pub fn push_bit(&mut self, bit: bool) -> Option<FramerEvent> {
+        self.shift = (self.shift >> 1) | if bit { 0x80 } else { 0 };
+
+        if let State::NoCarrier = self.state {
+            // SAME has no start/stop bits, so there's no guarantee
+            // the caller's first bit happens to land on a byte
+            // boundary. The preamble repeats the same byte many
+            // times over, so scan the shift register for it at
+            // every bit position instead of just every eighth one;
+            // whichever bit this is found on becomes byte zero for
+            // the rest of the burst
+            if self.shift == PREAMBLE_BYTE {
+                self.bit_count = 0;
+                self.state = State::Searching;
+                return Some(FramerEvent::Status(FramerStatus::Searching));
+            }
+            return None;
+        }
+
+        self.bit_count += 1;
+        if self.bit_count < 8 {
+            return None;
+        }
+        self.bit_count = 0;
+        let byte = self.shift;
+
+        match &mut self.state {
+            State::NoCarrier => unreachable!("handled above"),
+            State::Searching => {
+                if byte == PREAMBLE_BYTE {
+                    None
+                } else if byte == b'Z' {
+                    self.state = State::Reading(String::from("Z"));
+                    Some(FramerEvent::Status(FramerStatus::Reading))
+                } else {
+                    self.state = State::NoCarrier;
+                    Some(FramerEvent::Status(FramerStatus::NoCarrier))
+                }
+            }
+            State::Reading(text) => {
+                if !byte.is_ascii_graphic() && byte != b' ' {
+                    // carrier dropped mid-burst; report what we have
+                    let burst = std::mem::take(text);
+                    self.state = State::NoCarrier;
+                    return Some(FramerEvent::Burst(burst));
+                }
+
+                text.push(byte as char);
+
+                // the header's `+` begins the purge/issue/callsign
+                // tail, which always contains exactly three more
+                // hyphens: after TTTT, after JJJHHMM, and the
+                // burst's closing hyphen
+                let ends_burst = text
+                    .find('+')
+                    .map(|plus| text[plus..].matches('-').count() >= 3)
+                    .unwrap_or(false);
+
+                if ends_burst || text.len() >= MAX_BURST_LEN {
+                    let burst = std::mem::take(text);
+                    self.state = State::NoCarrier;
+                    Some(FramerEvent::Burst(burst))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+impl Default for Framer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_byte(framer: &mut Framer, byte: u8) -> Option<FramerEvent> {
+        let mut event = None;
+        for i in 0..8 {
+            let bit = (byte >> i) & 1 == 1;
+            event = framer.push_bit(bit).or(event);
+        }
+        event
+    }
+
+    #[test]
+    fn frames_a_complete_burst() {
+        let mut framer = Framer::new();
+
+        assert_eq!(
+            push_byte(&mut framer, PREAMBLE_BYTE),
+            Some(FramerEvent::Status(FramerStatus::Searching))
+        );
+
+        let header = "ZCZC-WXR-TOR-027037+0030-1231800-KGGG/NWS-";
+        let mut last = None;
+        for byte in header.bytes() {
+            if let Some(event) = push_byte(&mut framer, byte) {
+                last = Some(event);
+            }
+        }
+
+        assert_eq!(last, Some(FramerEvent::Burst(header.to_string())));
+    }
+}
@@ -0,0 +1,22 @@
+//! `sameold`: a decoder and encoder for the Specific Area Message
+//! Encoding (SAME) headers used by the US Emergency Alert System
+//! and NOAA Weather Radio.
+//!
+//! The [`receiver`] module demodulates and frames a SAME/EAS audio
+//! stream, reporting [`receiver::FrameOut`] events as they happen.
+//! [`transmit`] is its counterpart, rendering a [`Message`] back
+//! into SAME/EAS audio. [`message`] defines the decoded [`Message`]
+//! type that both sides share as the single source of truth for
+//! header field layout.
+
+pub mod demod;
+pub mod framer;
+pub mod message;
+pub mod receiver;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod transmit;
+
+pub use message::{IssueTime, Message, MessageDecodeErr, MessageResult};
+pub use receiver::{FrameOut, Receiver, TransportState};
+pub use transmit::SameEncoder;
@@ -0,0 +1,297 @@
+//! SAME/EAS message type
+//!
+//! A SAME burst is a hyphen-delimited ASCII header of the form
+//!
+//! ```text
+//! ZCZC-ORG-EEE-PSSCCC-PSSCCC+TTTT-JJJHHMM-LLLLLLLL-
+//! ```
+//!
+//! where `ORG` is the originator, `EEE` the event code, each
+//! `PSSCCC` a FIPS location code, `TTTT` the purge time offset
+//! (`hhmm`), `JJJHHMM` the issue time (day-of-year and `hhmm`
+//! UTC), and `LLLLLLLL` the originating station callsign. This
+//! module parses that header into a [`Message`] and renders a
+//! [`Message`] back into header text.
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// The issue time of a SAME header: day of year and time of day, UTC
+///
+/// Bundled into one type because the header's `JJJHHMM` field
+/// always carries all three together.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IssueTime {
+    /// Day of year (1-366)
+    pub day_of_year: u16,
+    /// Hour of day (0-23)
+    pub hour: u8,
+    /// Minute of hour (0-59)
+    pub minute: u8,
+}
+
+/// A decoded SAME/EAS header
+///
+/// `Message` is the single source of truth for SAME header field
+/// layout: both the receiver (decoding) and transmitter (encoding)
+/// sides of this crate build and consume this type.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Message {
+    originator: String,
+    event: String,
+    locations: Vec<String>,
+    purge: Duration,
+    issue: IssueTime,
+    callsign: String,
+}
+
+impl Message {
+    /// Construct a message from its component fields
+    ///
+    /// `purge` is rounded down to whole minutes, as required by
+    /// the `TTTT` field's `hhmm` encoding. `locations` must not be
+    /// empty: a SAME header always carries at least one FIPS
+    /// location code.
+    pub fn new(
+        originator: impl Into<String>,
+        event: impl Into<String>,
+        locations: impl IntoIterator<Item = impl Into<String>>,
+        purge: Duration,
+        issue: IssueTime,
+        callsign: impl Into<String>,
+    ) -> Self {
+        Message {
+            originator: originator.into(),
+            event: event.into(),
+            locations: locations.into_iter().map(Into::into).collect(),
+            purge: Duration::from_secs(purge.as_secs() / 60 * 60),
+            issue,
+            callsign: callsign.into(),
+        }
+    }
+
+    /// Originator code, e.g. `"WXR"`
+    pub fn originator(&self) -> &str {
+        &self.originator
+    }
+
+    /// Event code, e.g. `"TOR"`
+    pub fn event(&self) -> &str {
+        &self.event
+    }
+
+    /// FIPS location codes covered by this message
+    pub fn locations(&self) -> &[String] {
+        &self.locations
+    }
+
+    /// Purge time: how long this message remains valid
+    pub fn purge(&self) -> Duration {
+        self.purge
+    }
+
+    /// Day of year and time of day the message was issued, UTC
+    pub fn issue(&self) -> IssueTime {
+        self.issue
+    }
+
+    /// Originating station callsign, e.g. `"KGGG/NWS"`
+    pub fn callsign(&self) -> &str {
+        &self.callsign
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ZCZC-{}-{}-{}+{:02}{:02}-{:03}{:02}{:02}-{}-",
+            self.originator,
+            self.event,
+            self.locations.join("-"),
+            (self.purge.as_secs() / 60) / 60,
+            (self.purge.as_secs() / 60) % 60,
+            self.issue.day_of_year,
+            self.issue.hour,
+            self.issue.minute,
+            self.callsign,
+        )
+    }
+}
+
+impl FromStr for Message {
+    type Err = MessageDecodeErr;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let body = s
+            .trim()
+            .strip_prefix("ZCZC-")
+            .ok_or(MessageDecodeErr::MissingPreamble)?;
+        let body = body.strip_suffix('-').unwrap_or(body);
+
+        // the purge time's `+` separates the location list from
+        // the rest of the header
+        let (head, tail) = body.split_once('+').ok_or(MessageDecodeErr::BadPurgeTime)?;
+
+        let mut head_fields = head.split('-');
+        let originator = head_fields
+            .next()
+            .filter(|f| f.len() == 3)
+            .ok_or(MessageDecodeErr::BadOriginator)?
+            .to_string();
+        let event = head_fields
+            .next()
+            .filter(|f| f.len() == 3)
+            .ok_or(MessageDecodeErr::BadEvent)?
+            .to_string();
+        let locations: Vec<String> = head_fields.map(String::from).collect();
+        if locations.is_empty() {
+            return Err(MessageDecodeErr::NoLocations);
+        }
+        if locations
+            .iter()
+            .any(|loc| loc.len() != 6 || !loc.bytes().all(|b| b.is_ascii_digit()))
+        {
+            return Err(MessageDecodeErr::BadLocation);
+        }
+
+        let mut tail_fields = tail.splitn(3, '-');
+        let purge_field = tail_fields.next().ok_or(MessageDecodeErr::Truncated)?;
+        let issue = tail_fields.next().ok_or(MessageDecodeErr::Truncated)?;
+        let callsign = tail_fields
+            .next()
+            .filter(|f| !f.is_empty() && f.len() <= 8)
+            .ok_or(MessageDecodeErr::BadCallsign)?
+            .to_string();
+
+        let purge_minutes = parse_digits(purge_field, 4).ok_or(MessageDecodeErr::BadPurgeTime)?;
+        let purge = Duration::from_secs(
+            (purge_minutes / 100 * 60 + purge_minutes % 100) as u64 * 60,
+        );
+
+        let issue_digits = parse_digits(issue, 7).ok_or(MessageDecodeErr::BadIssueTime)?;
+        let issue = IssueTime {
+            day_of_year: (issue_digits / 10_000) as u16,
+            hour: ((issue_digits / 100) % 100) as u8,
+            minute: (issue_digits % 100) as u8,
+        };
+
+        Ok(Message {
+            originator,
+            event,
+            locations,
+            purge,
+            issue,
+            callsign,
+        })
+    }
+}
+
+/// Parse exactly `width` ASCII digits into an integer
+fn parse_digits(s: &str, width: usize) -> Option<u32> {
+    if s.len() != width || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+/// Reasons a SAME header failed to decode
+///
+/// A decode error indicates only that framing has failed *for the
+/// moment*: see [`crate::receiver::output::FrameOut::Ready`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MessageDecodeErr {
+    /// Burst did not begin with the `ZCZC-` preamble
+    MissingPreamble,
+    /// Originator field was missing or not three characters
+    BadOriginator,
+    /// Event field was missing or not three characters
+    BadEvent,
+    /// No location codes were present
+    NoLocations,
+    /// A location code was not six digits
+    BadLocation,
+    /// Purge time field was missing or not four digits
+    BadPurgeTime,
+    /// Issue time field was not seven digits (a missing issue time
+    /// field is reported as `Truncated`)
+    BadIssueTime,
+    /// Callsign field was missing, empty, or too long
+    BadCallsign,
+    /// Burst ended before all fields were read
+    Truncated,
+}
+
+impl fmt::Display for MessageDecodeErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            MessageDecodeErr::MissingPreamble => "missing \"ZCZC-\" preamble",
+            MessageDecodeErr::BadOriginator => "bad originator field",
+            MessageDecodeErr::BadEvent => "bad event field",
+            MessageDecodeErr::NoLocations => "no location codes present",
+            MessageDecodeErr::BadLocation => "bad location code",
+            MessageDecodeErr::BadPurgeTime => "bad purge time field",
+            MessageDecodeErr::BadIssueTime => "bad issue time field",
+            MessageDecodeErr::BadCallsign => "bad callsign field",
+            MessageDecodeErr::Truncated => "burst ended before all fields were read",
+        };
+        write!(f, "{}", msg)
+    }
+}
+
+impl std::error::Error for MessageDecodeErr {}
+
+/// Result of decoding a SAME header
+pub type MessageResult = Result<Message, MessageDecodeErr>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_display_and_from_str() {
+        let msg = Message::new(
+            "WXR",
+            "TOR",
+            vec!["027037", "027071"],
+            Duration::from_secs(30 * 60),
+            IssueTime {
+                day_of_year: 123,
+                hour: 18,
+                minute: 0,
+            },
+            "KGGG/NWS",
+        );
+
+        let rendered = msg.to_string();
+        let parsed: Message = rendered.parse().expect("parses own output");
+        assert_eq!(msg, parsed);
+    }
+
+    #[test]
+    fn new_rounds_purge_down_to_whole_minutes() {
+        let msg = Message::new(
+            "WXR",
+            "TOR",
+            vec!["027037"],
+            Duration::from_secs(30 * 60 + 59),
+            IssueTime {
+                day_of_year: 123,
+                hour: 18,
+                minute: 0,
+            },
+            "KGGG/NWS",
+        );
+
+        assert_eq!(msg.purge(), Duration::from_secs(30 * 60));
+    }
+
+    #[test]
+    fn rejects_missing_preamble() {
+        assert_eq!(
+            "WXR-TOR-027037+0030-1231800-KGGG-".parse::<Message>(),
+            Err(MessageDecodeErr::MissingPreamble)
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! [`tokio_util::codec::Decoder`] integration
+
+use std::io;
+use std::time::{Duration, Instant};
+
+use bytes::{Buf, BytesMut};
+use tokio_util::codec::Decoder;
+
+use super::{FrameOut, Receiver};
+
+/// Adapts [`Receiver`] to [`tokio_util::codec::Decoder`]
+///
+/// `SameDecoder` lets a [`Receiver`] be driven by any `AsyncRead`
+/// byte source -- a sound card, a TCP socket, or a file -- via
+/// [`tokio_util::codec::FramedRead`]:
+///
+/// ```ignore
+/// let mut frames = FramedRead::new(audio_socket, SameDecoder::new(sample_rate));
+/// while let Some(frame) = frames.next().await {
+///     // handle FrameOut
+/// }
+/// ```
+///
+/// Samples are read as signed 16-bit PCM, little-endian, at the
+/// sample rate given to [`SameDecoder::new()`]. Each call to
+/// `decode()` consumes whole samples from `src` until one produces a
+/// [`FrameOut`] state change, and returns it; the samples after it
+/// stay in `src` for the next call. If no state change is produced,
+/// every whole sample is consumed and any trailing partial sample is
+/// left in `src`, as the `Decoder` contract requires.
+///
+/// Decode errors reported by the receiver
+/// ([`FrameOut::Ready(Err(..))`](FrameOut::Ready)) are delivered as
+/// `Item`s, not `Error`s: per [`FrameOut`]'s documentation, they
+/// mean only that framing has failed *for the moment*, and the
+/// stream should keep running so a later burst can still decode.
+///
+/// The underlying [`Receiver`]'s hold-off deadline is driven by a
+/// clock derived from the count of samples consumed so far, not by
+/// `Instant::now()`: a `Decoder` is supposed to be a pure function
+/// of the bytes it's given, and wall-clock time would instead make
+/// the output depend on how fast `decode()` happens to be called.
+/// This matters most for a faster-than-real-time source such as a
+/// file, where the hold-off would otherwise never elapse inside
+/// `decode()` at all and every message would have to wait for
+/// [`SameDecoder::decode_eof`]. One limitation remains: `decode()`
+/// only runs when new bytes arrive, so a live source that stops
+/// sending data mid-message without reaching EOF will never finalize
+/// that message through `SameDecoder`. A caller that needs the
+/// hold-off to fire on a real timer despite a stalled source should
+/// drive a [`Receiver`] directly and schedule a wakeup from
+/// [`Receiver::time_to_finalize`] instead of going through
+/// `FramedRead`.
+pub struct SameDecoder {
+    receiver: Receiver,
+    base: Instant,
+    samples_read: u64,
+}
+
+impl SameDecoder {
+    /// Create a decoder for samples taken at `sample_rate` Hz
+    pub fn new(sample_rate: u32) -> Self {
+        SameDecoder {
+            receiver: Receiver::new(sample_rate),
+            base: Instant::now(),
+            samples_read: 0,
+        }
+    }
+
+    /// Create a decoder for samples taken at `sample_rate` Hz, with
+    /// a custom transport hold-off; see [`Receiver::with_hold_off`]
+    pub fn with_hold_off(sample_rate: u32, hold_off: Duration) -> Self {
+        SameDecoder {
+            receiver: Receiver::with_hold_off(sample_rate, hold_off),
+            base: Instant::now(),
+            samples_read: 0,
+        }
+    }
+
+    /// The sample rate this decoder was constructed with
+    pub fn sample_rate(&self) -> u32 {
+        self.receiver.sample_rate()
+    }
+
+    /// Push one sample, advancing the receiver's clock by one
+    /// sample period rather than by wall-clock time
+    fn push_sample(&mut self, sample: i16) -> Option<FrameOut> {
+        let elapsed = Duration::from_secs_f64(self.samples_read as f64 / self.sample_rate() as f64);
+        self.samples_read += 1;
+        self.receiver.push_sample_at(sample, self.base + elapsed)
+    }
+}
+
+impl Decoder for SameDecoder {
+    type Item = FrameOut;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> io::Result<Option<FrameOut>> {
+        const SAMPLE_LEN: usize = std::mem::size_of::<i16>();
+
+        while src.len() >= SAMPLE_LEN {
+            let sample = src.get_i16_le();
+            if let Some(out) = self.push_sample(sample) {
+                return Ok(Some(out));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> io::Result<Option<FrameOut>> {
+        if let Some(out) = self.decode(src)? {
+            return Ok(Some(out));
+        }
+
+        // no more samples are coming: give the transport assembler
+        // a chance to finalize an in-progress message rather than
+        // silently dropping it
+        Ok(self.receiver.flush())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::BufMut;
+
+    use crate::test_support::{sample_message, SAMPLE_RATE};
+    use crate::transmit::SameEncoder;
+
+    /// A real SAME message, rendered to audio
+    ///
+    /// `decode_is_independent_of_chunk_boundaries` relies on this
+    /// actually producing `FrameOut::Ready` events -- it's the
+    /// `SameDecoder` counterpart to the round trip tested against
+    /// `SameEncoder` directly in [`crate::transmit::encoder`].
+    fn recording() -> Vec<i16> {
+        let message = sample_message();
+
+        let mut encoder = SameEncoder::new(SAMPLE_RATE);
+        encoder.set_attention_tone(false);
+        let mut samples = Vec::new();
+        encoder.encode_to(&message, &mut samples);
+        samples
+    }
+
+    fn to_bytes(samples: &[i16]) -> BytesMut {
+        let mut buf = BytesMut::with_capacity(samples.len() * 2);
+        for sample in samples {
+            buf.put_i16_le(*sample);
+        }
+        buf
+    }
+
+    /// Feed `src` through a fresh decoder, `chunk` bytes at a time
+    fn decode_all(mut src: BytesMut, chunk: usize) -> Vec<FrameOut> {
+        let mut decoder = SameDecoder::new(SAMPLE_RATE);
+        let mut out = Vec::new();
+        let mut pending = BytesMut::new();
+
+        while !src.is_empty() {
+            let take = chunk.min(src.len());
+            pending.unsplit(src.split_to(take));
+            while let Some(frame) = decoder.decode(&mut pending).expect("decode") {
+                out.push(frame);
+            }
+        }
+        // no more samples are coming: give the transport assembler a
+        // chance to finalize, the same way a real `FramedRead` does
+        // when its underlying source reaches EOF
+        if let Some(frame) = decoder.decode_eof(&mut pending).expect("decode_eof") {
+            out.push(frame);
+        }
+
+        out
+    }
+
+    // `SameDecoder`'s hold-off clock is derived from the count of
+    // samples consumed, not from wall-clock time, so this holds
+    // regardless of how long each `decode_all` call takes to run.
+    #[test]
+    fn decode_is_independent_of_chunk_boundaries() {
+        let bytes = to_bytes(&recording());
+
+        let whole = decode_all(bytes.clone(), usize::MAX);
+        let one_byte_at_a_time = decode_all(bytes.clone(), 1);
+        let three_bytes_at_a_time = decode_all(bytes.clone(), 3);
+        let one_frame_at_a_time = decode_all(bytes, 512);
+
+        let ready_events = whole
+            .iter()
+            .filter(|frame| matches!(frame, FrameOut::Ready(_)))
+            .count();
+        assert!(
+            ready_events > 0,
+            "a real SAME recording should produce at least one Ready event"
+        );
+
+        assert_eq!(whole, one_byte_at_a_time);
+        assert_eq!(whole, three_bytes_at_a_time);
+        assert_eq!(whole, one_frame_at_a_time);
+    }
+}
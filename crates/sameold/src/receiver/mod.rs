@@ -0,0 +1,202 @@
+//! SAME/EAS receiver
+//!
+//! [`Receiver`] is the front end described in [`output`]'s module
+//! documentation: it demodulates Bell 202 AFSK samples
+//! ([`crate::demod`]), frames them into bursts
+//! ([`crate::framer`]), and assembles bursts into messages
+//! ([`transport`]), reporting each state change as a [`FrameOut`].
+
+pub mod decoder;
+pub mod output;
+pub mod transport;
+
+pub use output::{FrameOut, TransportState};
+
+use std::time::{Duration, Instant};
+
+use crate::demod::Demodulator;
+use crate::framer::{Framer, FramerEvent, FramerStatus};
+
+use transport::Transport;
+
+/// Demodulates, frames, and assembles a SAME/EAS audio stream
+///
+/// `Receiver` owns all of the DSP state needed to decode SAME: push
+/// samples one at a time with [`Receiver::push_sample`] and receive
+/// a [`FrameOut`] each time the framing status changes or a message
+/// is assembled. [`Receiver::push_sample_at`] takes a caller-supplied
+/// clock instead of reading `Instant::now()`, so the whole pipeline
+/// can be driven deterministically in tests.
+pub struct Receiver {
+    sample_rate: u32,
+    demod: Demodulator,
+    framer: Framer,
+    transport: Transport,
+}
+
+impl Receiver {
+    /// Create a receiver for samples taken at `sample_rate` Hz
+    pub fn new(sample_rate: u32) -> Self {
+        Self::with_hold_off(sample_rate, Transport::new().hold_off())
+    }
+
+    /// Create a receiver for samples taken at `sample_rate` Hz, with
+    /// a custom transport hold-off
+    ///
+    /// `hold_off` is how long the transport assembler waits after
+    /// the most recent burst before finalizing a message; see
+    /// [`transport::Transport::with_hold_off`]. Embedding an event
+    /// loop that wants a tighter or looser deadline than the default
+    /// should use this instead of [`Receiver::new`].
+    pub fn with_hold_off(sample_rate: u32, hold_off: Duration) -> Self {
+        Receiver {
+            sample_rate,
+            demod: Demodulator::new(sample_rate),
+            framer: Framer::new(),
+            transport: Transport::with_hold_off(hold_off),
+        }
+    }
+
+    /// The sample rate this receiver was constructed with
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Push one signed 16-bit PCM sample
+    ///
+    /// Returns `Some` each time the framing status changes or a
+    /// message is assembled; returns `None` otherwise.
+    pub fn push_sample(&mut self, sample: i16) -> Option<FrameOut> {
+        self.push_sample_at(sample, Instant::now())
+    }
+
+    /// Like [`Receiver::push_sample`], but using a caller-supplied clock
+    ///
+    /// Threads `now` through to the underlying
+    /// [`transport::Transport`]'s own `_at` methods, for the same
+    /// reason described on [`transport::Transport::poll_at`]: it
+    /// lets the whole receiver, not just the transport, be driven
+    /// deterministically by a mock clock.
+    pub fn push_sample_at(&mut self, sample: i16, now: Instant) -> Option<FrameOut> {
+        if let Some(bit) = self.demod.push_sample(sample) {
+            if let Some(event) = self.framer.push_bit(bit) {
+                return Some(self.handle_framer_event(event, now));
+            }
+        }
+
+        // even without a fresh framer event, the transport's
+        // hold-off deadline may have elapsed since the last sample
+        if let TransportState::Message(result) = self.transport.poll_at(now) {
+            return Some(FrameOut::Ready(result));
+        }
+
+        None
+    }
+
+    /// Tell the receiver that no more samples are coming
+    ///
+    /// Flushes the transport assembler immediately rather than
+    /// waiting for its hold-off deadline, so a finite recording
+    /// that ends right after its last burst still produces a
+    /// [`FrameOut::Ready`]. Returns `None` if there was nothing to
+    /// flush.
+    pub fn flush(&mut self) -> Option<FrameOut> {
+        match self.transport.flush() {
+            TransportState::Message(result) => Some(FrameOut::Ready(result)),
+            _ => None,
+        }
+    }
+
+    /// Time remaining until the transport assembler will finalize a
+    /// message on its own, as of `now`
+    ///
+    /// Returns `None` when there is nothing pending -- i.e. no
+    /// burst has been read since the last message was reported.
+    /// Callers driving this receiver from a `tokio::time` select
+    /// loop can use this to schedule a wakeup instead of polling,
+    /// and a mock `now` to exercise hold-off behavior
+    /// deterministically in tests.
+    pub fn time_to_finalize(&self, now: Instant) -> Option<Duration> {
+        self.transport.time_to_finalize(now)
+    }
+
+    /// Advance the transport assembler's hold-off deadline using a
+    /// caller-supplied clock, finalizing a message if `now` has
+    /// passed the deadline returned by [`Receiver::time_to_finalize`]
+    pub fn poll_at(&mut self, now: Instant) -> Option<FrameOut> {
+        match self.transport.poll_at(now) {
+            TransportState::Message(result) => Some(FrameOut::Ready(result)),
+            _ => None,
+        }
+    }
+
+    fn handle_framer_event(&mut self, event: FramerEvent, now: Instant) -> FrameOut {
+        match event {
+            FramerEvent::Status(FramerStatus::NoCarrier) => FrameOut::NoCarrier,
+            FramerEvent::Status(FramerStatus::Searching) => FrameOut::Searching,
+            FramerEvent::Status(FramerStatus::Reading) => FrameOut::Reading,
+            FramerEvent::Burst(burst) => match self.transport.push_burst_at(burst, now) {
+                TransportState::Message(result) => FrameOut::Ready(result),
+                _ => FrameOut::Reading,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{sample_message, SAMPLE_RATE};
+    use crate::transmit::SameEncoder;
+
+    #[test]
+    fn push_sample_at_finalizes_on_a_mock_clock() {
+        let message = sample_message();
+
+        let mut encoder = SameEncoder::new(SAMPLE_RATE);
+        encoder.set_attention_tone(false);
+        encoder.set_end_of_message(false);
+        let mut samples = Vec::new();
+        encoder.encode_to(&message, &mut samples);
+
+        let mut receiver = Receiver::new(SAMPLE_RATE);
+        let t0 = Instant::now();
+
+        let mut ready_while_feeding = false;
+        for sample in samples {
+            if let Some(FrameOut::Ready(_)) = receiver.push_sample_at(sample, t0) {
+                ready_while_feeding = true;
+            }
+        }
+        // the hold-off deadline is anchored to `t0`, so feeding every
+        // sample at `t0` must not finalize a message on its own
+        assert!(!ready_while_feeding);
+
+        match receiver.push_sample_at(0, t0 + Duration::from_secs(5)) {
+            Some(FrameOut::Ready(Ok(decoded))) => assert_eq!(decoded, message),
+            other => panic!("expected the mock clock to finalize the message, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn with_hold_off_uses_a_custom_deadline() {
+        let message = sample_message();
+
+        let mut encoder = SameEncoder::new(SAMPLE_RATE);
+        encoder.set_attention_tone(false);
+        encoder.set_end_of_message(false);
+        let mut samples = Vec::new();
+        encoder.encode_to(&message, &mut samples);
+
+        let hold_off = Duration::from_millis(100);
+        let mut receiver = Receiver::with_hold_off(SAMPLE_RATE, hold_off);
+        let t0 = Instant::now();
+
+        for sample in samples {
+            receiver.push_sample_at(sample, t0);
+        }
+
+        // the default hold-off (1.5s) would not have elapsed yet
+        assert!(receiver.poll_at(t0 + Duration::from_millis(200)).is_some());
+    }
+}
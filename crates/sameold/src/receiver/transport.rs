@@ -0,0 +1,255 @@
+//! Transport assembler
+//!
+//! [`Transport`] implements the [`TransportState`] state machine
+//! described in [`crate::receiver::output`]: it collects one or
+//! more decoded bursts and, once no more are expected, assembles
+//! them into a [`Message`].
+
+use std::time::{Duration, Instant};
+
+use crate::message::{Message, MessageDecodeErr, MessageResult};
+
+use super::output::TransportState;
+
+/// How long to wait after the last burst before finalizing a message
+///
+/// SAME repeats each burst three times with roughly one-second
+/// gaps; this is comfortably longer than that gap.
+const DEFAULT_HOLD_OFF: Duration = Duration::from_millis(1_500);
+
+/// Collects repeated bursts into a single [`Message`]
+pub struct Transport {
+    state: TransportState,
+    bursts: Vec<String>,
+    hold_off: Duration,
+    deadline: Option<Instant>,
+}
+
+impl Transport {
+    /// Create a new, idle transport assembler with the default hold-off
+    pub fn new() -> Self {
+        Self::with_hold_off(DEFAULT_HOLD_OFF)
+    }
+
+    /// Create a new, idle transport assembler with a custom hold-off
+    ///
+    /// `hold_off` is how long the assembler waits after the most
+    /// recent burst before finalizing a message. The default
+    /// ([`Transport::new`]) is comfortably longer than the ~1 second
+    /// gap SAME leaves between repeated bursts.
+    pub fn with_hold_off(hold_off: Duration) -> Self {
+        Transport {
+            state: TransportState::Idle,
+            bursts: Vec::with_capacity(3),
+            hold_off,
+            deadline: None,
+        }
+    }
+
+    /// The current transport state
+    pub fn state(&self) -> &TransportState {
+        &self.state
+    }
+
+    /// The hold-off this transport was constructed with
+    pub fn hold_off(&self) -> Duration {
+        self.hold_off
+    }
+
+    /// Time remaining until the current `Assembling` state will
+    /// produce a `Message`, as of `now`
+    ///
+    /// Returns `None` when the assembler is not
+    /// [`TransportState::Assembling`] -- there is nothing pending
+    /// to finalize, so there is no deadline to wait for. A duration
+    /// of zero means the deadline has already passed and the next
+    /// [`Transport::poll_at`] or [`Transport::poll`] call will
+    /// finalize it.
+    ///
+    /// Callers embedding this in an async event loop can use this
+    /// to schedule a wakeup (e.g. `tokio::time::sleep`) instead of
+    /// polling in a busy loop.
+    pub fn time_to_finalize(&self, now: Instant) -> Option<Duration> {
+        self.deadline.map(|deadline| deadline.saturating_duration_since(now))
+    }
+
+    /// Record a newly-decoded burst
+    ///
+    /// Transitions to [`TransportState::Assembling`] and (re)starts
+    /// the hold-off deadline. Call [`Transport::poll`] to check
+    /// whether the deadline has since passed.
+    pub fn push_burst(&mut self, burst: String) -> TransportState {
+        self.push_burst_at(burst, Instant::now())
+    }
+
+    /// Like [`Transport::push_burst`], but using a caller-supplied clock
+    pub fn push_burst_at(&mut self, burst: String, now: Instant) -> TransportState {
+        self.bursts.push(burst);
+        self.state = TransportState::Assembling;
+        self.deadline = Some(now + self.hold_off);
+        self.state.clone()
+    }
+
+    /// Check whether the hold-off deadline has passed
+    ///
+    /// If it has, and the transport was [`TransportState::Assembling`],
+    /// this finalizes the accumulated bursts into a `Message` and
+    /// returns the new [`TransportState::Message`].
+    pub fn poll(&mut self) -> TransportState {
+        self.poll_at(Instant::now())
+    }
+
+    /// Like [`Transport::poll`], but using a caller-supplied clock
+    ///
+    /// This decouples finalization from any implicit
+    /// `Instant::now()` call inside the crate, so the assembler can
+    /// be driven deterministically by a mock clock in tests, or by
+    /// whatever clock an embedding event loop already has on hand.
+    pub fn poll_at(&mut self, now: Instant) -> TransportState {
+        if self.deadline.map(|d| now >= d).unwrap_or(false) {
+            self.finalize()
+        } else {
+            self.state.clone()
+        }
+    }
+
+    /// Tell the assembler that no more bursts are coming
+    ///
+    /// Unlike [`Transport::poll`], `flush` does not wait for the
+    /// hold-off deadline: if the assembler is
+    /// [`TransportState::Assembling`], it immediately finalizes
+    /// whatever bursts have been accumulated and returns the
+    /// resulting [`TransportState::Message`]. This lets a caller
+    /// reading a finite recording -- one that ends right after the
+    /// last burst, before the deadline would otherwise elapse --
+    /// still get a message instead of a silently dropped
+    /// `Assembling` state.
+    ///
+    /// Has no effect on [`TransportState::Idle`], including just
+    /// after a [`TransportState::Message`] has already been
+    /// reported.
+    pub fn flush(&mut self) -> TransportState {
+        match self.state {
+            TransportState::Assembling => self.finalize(),
+            _ => self.state.clone(),
+        }
+    }
+
+    /// Finalize whatever bursts have been accumulated so far
+    ///
+    /// A [`TransportState::Message`] is an edge-triggered report,
+    /// not a resting state: once built, the assembler resets to
+    /// [`TransportState::Idle`] so that a later, unrelated call to
+    /// [`Transport::poll`] or [`Transport::flush`] does not
+    /// re-deliver the same message.
+    fn finalize(&mut self) -> TransportState {
+        let result = assemble(&self.bursts);
+        self.bursts.clear();
+        self.deadline = None;
+        self.state = TransportState::Idle;
+        TransportState::Message(result)
+    }
+}
+
+impl Default for Transport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Assemble accumulated burst strings into a single decode result
+///
+/// Each burst is decoded independently; the first burst that
+/// decodes successfully wins. If none do, the last burst's error is
+/// reported, since later bursts are often the cleanest copy.
+fn assemble(bursts: &[String]) -> MessageResult {
+    let mut last_err = MessageDecodeErr::Truncated;
+    for burst in bursts {
+        match burst.parse::<Message>() {
+            Ok(message) => return Ok(message),
+            Err(e) => last_err = e,
+        }
+    }
+    Err(last_err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::sleep;
+
+    #[test]
+    fn starts_idle() {
+        let transport = Transport::new();
+        assert_eq!(transport.state(), &TransportState::Idle);
+    }
+
+    #[test]
+    fn assembles_after_hold_off_elapses() {
+        let mut transport = Transport::with_hold_off(Duration::from_millis(10));
+
+        assert_eq!(
+            transport.push_burst("ZCZC-WXR-TOR-027037+0030-1231800-KGGG/NWS-".to_string()),
+            TransportState::Assembling
+        );
+        assert_eq!(transport.poll(), TransportState::Assembling);
+
+        sleep(Duration::from_millis(20));
+
+        match transport.poll() {
+            TransportState::Message(Ok(message)) => assert_eq!(message.event(), "TOR"),
+            other => panic!("expected a decoded message, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn flush_finalizes_a_recording_truncated_after_two_bursts() {
+        // a long hold-off that `flush` must not wait for
+        let mut transport = Transport::with_hold_off(Duration::from_secs(60));
+
+        let burst = "ZCZC-WXR-TOR-027037+0030-1231800-KGGG/NWS-".to_string();
+        transport.push_burst(burst.clone());
+        assert_eq!(transport.poll(), TransportState::Assembling);
+        transport.push_burst(burst);
+        assert_eq!(transport.poll(), TransportState::Assembling);
+
+        match transport.flush() {
+            TransportState::Message(Ok(message)) => assert_eq!(message.event(), "TOR"),
+            other => panic!("expected flush to finalize immediately, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn flush_is_a_no_op_when_idle() {
+        let mut transport = Transport::new();
+        assert_eq!(transport.flush(), TransportState::Idle);
+    }
+
+    #[test]
+    fn time_to_finalize_is_none_when_idle() {
+        let transport = Transport::new();
+        assert_eq!(transport.time_to_finalize(Instant::now()), None);
+    }
+
+    #[test]
+    fn mock_clock_drives_poll_at_deterministically() {
+        let hold_off = Duration::from_secs(2);
+        let mut transport = Transport::with_hold_off(hold_off);
+        let t0 = Instant::now();
+
+        let burst = "ZCZC-WXR-TOR-027037+0030-1231800-KGGG/NWS-".to_string();
+        transport.push_burst_at(burst, t0);
+
+        assert_eq!(transport.time_to_finalize(t0), Some(hold_off));
+        assert_eq!(transport.poll_at(t0 + hold_off / 2), TransportState::Assembling);
+        assert_eq!(
+            transport.time_to_finalize(t0 + hold_off / 2),
+            Some(hold_off / 2)
+        );
+
+        match transport.poll_at(t0 + hold_off) {
+            TransportState::Message(Ok(message)) => assert_eq!(message.event(), "TOR"),
+            other => panic!("expected the mock clock to finalize the message, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! Shared test fixtures
+//!
+//! Encoder, decoder, and receiver tests all round-trip the same
+//! representative message; keeping it here means a change to the
+//! fixture doesn't have to be copied into every test module that
+//! uses it.
+#![cfg(test)]
+
+use std::time::Duration;
+
+use crate::message::IssueTime;
+use crate::Message;
+
+/// Sample rate shared by this crate's tests
+pub(crate) const SAMPLE_RATE: u32 = 22_050;
+
+/// A representative SAME message used across the encoder, decoder,
+/// and receiver test suites
+pub(crate) fn sample_message() -> Message {
+    Message::new(
+        "WXR",
+        "TOR",
+        vec!["027037"],
+        Duration::from_secs(30 * 60),
+        IssueTime {
+            day_of_year: 123,
+            hour: 18,
+            minute: 0,
+        },
+        "KGGG/NWS",
+    )
+}
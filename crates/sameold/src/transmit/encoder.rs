@@ -0,0 +1,230 @@
+//! [`tokio_util::codec::Encoder`] integration
+
+use std::io;
+use std::time::Duration;
+
+use bytes::{BufMut, BytesMut};
+use tokio_util::codec::Encoder;
+
+use crate::demod::{samples_per_bit, MARK_HZ, SPACE_HZ};
+use crate::framer::PREAMBLE_BYTE;
+use crate::Message;
+
+/// Number of preamble bytes sent ahead of every burst
+const PREAMBLE_LEN: usize = 16;
+
+/// Gap of silence between repeated bursts
+///
+/// SAME leaves roughly one second of silence between each of a
+/// burst's three repetitions.
+const BURST_GAP: Duration = Duration::from_secs(1);
+
+/// Duration of the EAS two-tone attention signal
+const ATTENTION_TONE_DURATION: Duration = Duration::from_secs(8);
+
+/// The EAS dual attention tone frequencies, Hz
+const ATTENTION_TONE_HZ: (f32, f32) = (853.0, 960.0);
+
+/// Peak amplitude used for generated tones, leaving headroom below
+/// full scale
+const AMPLITUDE: f32 = 0.9 * i16::MAX as f32;
+
+/// Renders a [`Message`] as Bell 202 AFSK audio
+///
+/// `SameEncoder` is the counterpart to
+/// [`SameDecoder`](crate::receiver::decoder::SameDecoder): it formats
+/// the full SAME header (preamble, `ZCZC-...` fields, and `NNNN`
+/// end-of-message), renders it as audio at 520.83 baud using a
+/// 2083.3 Hz mark / 1562.5 Hz space tone, and repeats each burst
+/// three times with the one-second gaps SAME expects. The
+/// [`Message`] is the single source of truth for header field
+/// layout, shared with [`crate::receiver`]'s decode path.
+pub struct SameEncoder {
+    sample_rate: u32,
+    attention_tone: bool,
+    end_of_message: bool,
+    phase: f32,
+}
+
+impl SameEncoder {
+    /// Create an encoder rendering samples at `sample_rate` Hz
+    ///
+    /// The EAS attention tone and the `NNNN` end-of-message bursts
+    /// are both included by default; disable them with
+    /// [`SameEncoder::set_attention_tone`] and
+    /// [`SameEncoder::set_end_of_message`].
+    pub fn new(sample_rate: u32) -> Self {
+        SameEncoder {
+            sample_rate,
+            attention_tone: true,
+            end_of_message: true,
+            phase: 0.0,
+        }
+    }
+
+    /// The sample rate this encoder was constructed with
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Whether the EAS two-tone attention signal is appended after
+    /// the header bursts
+    pub fn set_attention_tone(&mut self, enabled: bool) -> &mut Self {
+        self.attention_tone = enabled;
+        self
+    }
+
+    /// Whether the `NNNN` end-of-message bursts are appended after
+    /// the header bursts (and the attention tone, if enabled)
+    pub fn set_end_of_message(&mut self, enabled: bool) -> &mut Self {
+        self.end_of_message = enabled;
+        self
+    }
+
+    /// Render `message` as PCM samples, appending to `samples`
+    ///
+    /// Synchronous counterpart to the [`Encoder<Message>`] impl, for
+    /// callers that aren't driving a `Sink`.
+    pub fn encode_to(&mut self, message: &Message, samples: &mut Vec<i16>) {
+        let samples_per_bit = samples_per_bit(self.sample_rate);
+        let header = message.to_string();
+
+        for _ in 0..3 {
+            self.encode_burst(&header, samples_per_bit, samples);
+            self.encode_silence(BURST_GAP, samples);
+        }
+
+        if self.attention_tone {
+            self.encode_attention_tone(samples);
+            self.encode_silence(BURST_GAP, samples);
+        }
+
+        if self.end_of_message {
+            for _ in 0..3 {
+                self.encode_burst("NNNN", samples_per_bit, samples);
+                self.encode_silence(BURST_GAP, samples);
+            }
+        }
+    }
+
+    /// Encode one burst: preamble, then `body`'s ASCII bytes
+    fn encode_burst(&mut self, body: &str, samples_per_bit: usize, samples: &mut Vec<i16>) {
+        for _ in 0..PREAMBLE_LEN {
+            self.encode_byte(PREAMBLE_BYTE, samples_per_bit, samples);
+        }
+        for byte in body.bytes() {
+            self.encode_byte(byte, samples_per_bit, samples);
+        }
+    }
+
+    /// Encode one NRZ byte, LSB first, as mark/space AFSK tones
+    fn encode_byte(&mut self, byte: u8, samples_per_bit: usize, samples: &mut Vec<i16>) {
+        for bit_index in 0..8 {
+            let bit = (byte >> bit_index) & 1 == 1;
+            let freq_hz = if bit { MARK_HZ } else { SPACE_HZ };
+            self.encode_tone(freq_hz, samples_per_bit, samples);
+        }
+    }
+
+    /// Encode `n` samples of a continuous-phase sine tone at `freq_hz`
+    fn encode_tone(&mut self, freq_hz: f32, n: usize, samples: &mut Vec<i16>) {
+        let step = 2.0 * std::f32::consts::PI * freq_hz / self.sample_rate as f32;
+        for _ in 0..n {
+            samples.push((self.phase.sin() * AMPLITUDE) as i16);
+            self.phase = (self.phase + step) % (2.0 * std::f32::consts::PI);
+        }
+    }
+
+    /// Encode the EAS dual attention tone
+    fn encode_attention_tone(&mut self, samples: &mut Vec<i16>) {
+        let (freq_a, freq_b) = ATTENTION_TONE_HZ;
+        let step_a = 2.0 * std::f32::consts::PI * freq_a / self.sample_rate as f32;
+        let step_b = 2.0 * std::f32::consts::PI * freq_b / self.sample_rate as f32;
+        let n = (self.sample_rate as f32 * ATTENTION_TONE_DURATION.as_secs_f32()) as usize;
+
+        let (mut phase_a, mut phase_b) = (0.0f32, 0.0f32);
+        for _ in 0..n {
+            let s = 0.5 * (phase_a.sin() + phase_b.sin());
+            samples.push((s * AMPLITUDE) as i16);
+            phase_a = (phase_a + step_a) % (2.0 * std::f32::consts::PI);
+            phase_b = (phase_b + step_b) % (2.0 * std::f32::consts::PI);
+        }
+    }
+
+    /// Encode `duration` worth of silence
+    fn encode_silence(&self, duration: Duration, samples: &mut Vec<i16>) {
+        let n = (self.sample_rate as f32 * duration.as_secs_f32()) as usize;
+        samples.resize(samples.len() + n, 0);
+    }
+}
+
+impl Encoder<Message> for SameEncoder {
+    type Error = io::Error;
+
+    fn encode(&mut self, message: Message, dst: &mut BytesMut) -> io::Result<()> {
+        let mut samples = Vec::new();
+        self.encode_to(&message, &mut samples);
+
+        dst.reserve(samples.len() * std::mem::size_of::<i16>());
+        for sample in samples {
+            dst.put_i16_le(sample);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::receiver::decoder::SameDecoder;
+    use crate::receiver::output::FrameOut;
+    use crate::test_support::{sample_message, SAMPLE_RATE};
+    use tokio_util::codec::Decoder;
+
+    #[test]
+    fn round_trips_through_the_decoder() {
+        let message = sample_message();
+
+        let mut encoder = SameEncoder::new(SAMPLE_RATE);
+        encoder.set_attention_tone(false);
+        let mut samples = Vec::new();
+        encoder.encode_to(&message, &mut samples);
+
+        let mut bytes = BytesMut::with_capacity(samples.len() * 2);
+        for sample in &samples {
+            bytes.put_i16_le(*sample);
+        }
+
+        let mut decoder = SameDecoder::new(SAMPLE_RATE);
+        let mut ready = Vec::new();
+        while let Some(frame) = decoder.decode(&mut bytes).expect("decode") {
+            if let FrameOut::Ready(result) = frame {
+                ready.push(result);
+            }
+        }
+        if let Some(FrameOut::Ready(result)) = decoder.decode_eof(&mut bytes).expect("decode_eof") {
+            ready.push(result);
+        }
+
+        assert_eq!(ready.last(), Some(&Ok(message)));
+    }
+
+    #[test]
+    fn end_of_message_can_be_disabled() {
+        let message = sample_message();
+
+        let mut with_eom = SameEncoder::new(SAMPLE_RATE);
+        with_eom.set_attention_tone(false);
+        let mut with_eom_samples = Vec::new();
+        with_eom.encode_to(&message, &mut with_eom_samples);
+
+        let mut without_eom = SameEncoder::new(SAMPLE_RATE);
+        without_eom.set_attention_tone(false);
+        without_eom.set_end_of_message(false);
+        let mut without_eom_samples = Vec::new();
+        without_eom.encode_to(&message, &mut without_eom_samples);
+
+        assert!(without_eom_samples.len() < with_eom_samples.len());
+    }
+}
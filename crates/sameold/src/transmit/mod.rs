@@ -0,0 +1,11 @@
+//! SAME/EAS transmit
+//!
+//! The counterpart to [`crate::receiver`]: renders a [`crate::Message`]
+//! as Bell 202 AFSK audio, the way a real SAME/EAS encoder would key
+//! a transmitter. Pairs with [`crate::receiver::decoder::SameDecoder`]
+//! the way [`tokio_util::codec::Framed`] pairs an `Encoder` with a
+//! `Decoder`.
+
+pub mod encoder;
+
+pub use encoder::SameEncoder;